@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use super::{touch_group::TouchGroup, TouchStatus};
+
+/// Thresholds for [`NoiseFilter`], resolved from [`TouchListener`]'s atomics
+/// once per sync frame
+///
+/// [`TouchListener`]: crate::TouchListener
+#[derive(Debug, Clone, Copy)]
+pub struct FilterConfig {
+    /// Minimum lifetime, in sync frames, before a contact is trusted
+    pub min_lifetime: usize,
+    /// Drop contacts whose major-axis/pressure exceeds this (`0` disables palm rejection)
+    pub palm_major: usize,
+    /// Number of consecutive monotone frames required before declaring a slide
+    pub debounce: usize,
+    /// Per-frame pixel delta that counts as movement (mirrors `min_pixel`)
+    pub min_pixel: usize,
+}
+
+#[derive(Debug, Default)]
+struct SlotState {
+    frames: usize,
+    prev: Option<(i32, i32)>,
+    monotone: usize,
+    sign: (i32, i32),
+    accepted: bool,
+    sliding: bool,
+}
+
+/// Rejects transient noise, palm contacts and jittery micro-movements before
+/// the group reaches [`analyze`]
+///
+/// Inspired by the ChromeOS `touch_noise_monitor`: a contact must survive
+/// `min_lifetime` frames and stay below the palm size threshold to be
+/// accepted, and must move monotonically for `debounce` frames before it is
+/// reported as sliding.
+///
+/// [`analyze`]: crate::analyze::analyze
+#[derive(Debug, Default)]
+pub struct NoiseFilter {
+    slots: HashMap<Option<i32>, SlotState>,
+}
+
+impl NoiseFilter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one sync frame into the per-slot acceptance/slide snapshot
+    pub fn observe(&mut self, group: &TouchGroup, cfg: &FilterConfig) {
+        self.slots.retain(|slot, _| group.slots.contains_key(slot));
+
+        for (slot, contact) in &group.slots {
+            let pos = &contact.pos;
+            let (Some(x), Some(y)) = pos.cur_pos else {
+                continue;
+            };
+
+            let state = self.slots.entry(*slot).or_default();
+            state.frames += 1;
+
+            let palm = cfg.palm_major != 0
+                && pos.major.is_some_and(|m| m as usize > cfg.palm_major);
+            state.accepted = !palm && state.frames >= cfg.min_lifetime;
+
+            if let Some((px, py)) = state.prev {
+                let (dx, dy) = (x - px, y - py);
+                let moved = f64::from(dx * dx + dy * dy).sqrt().round() as usize >= cfg.min_pixel;
+                let sign = (dx.signum(), dy.signum());
+
+                if moved && continues(state.sign, sign) {
+                    state.monotone += 1;
+                    state.sign = sign;
+                } else if moved {
+                    state.monotone = 1;
+                    state.sign = sign;
+                } else {
+                    state.monotone = 0;
+                }
+            }
+
+            state.sliding = state.accepted && state.monotone >= cfg.debounce;
+            state.prev = Some((x, y));
+        }
+    }
+
+    /// Comprehensive status of all accepted contacts
+    ///
+    /// Rejected (noise/palm) contacts are invisible here, so a screen holding
+    /// only a palm reports [`TouchStatus::None`].
+    #[must_use]
+    pub fn status(&self) -> TouchStatus {
+        let mut live = false;
+
+        for state in self.slots.values().filter(|s| s.accepted) {
+            live = true;
+            if state.sliding {
+                return TouchStatus::Slide;
+            }
+        }
+
+        if live {
+            TouchStatus::Click
+        } else {
+            TouchStatus::None
+        }
+    }
+}
+
+/// Whether `next` keeps the same per-axis direction as `prev` (treating a zero
+/// previous sign as compatible)
+fn continues(prev: (i32, i32), next: (i32, i32)) -> bool {
+    (prev.0 == 0 || prev.0 == next.0) && (prev.1 == 0 || prev.1 == next.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FilterConfig, NoiseFilter};
+    use crate::touch_group::TouchGroup;
+    use crate::TouchStatus;
+    use std::time::Duration;
+
+    fn cfg(min_lifetime: usize, palm_major: usize, debounce: usize, min_pixel: usize) -> FilterConfig {
+        FilterConfig {
+            min_lifetime,
+            palm_major,
+            debounce,
+            min_pixel,
+        }
+    }
+
+    fn place(group: &mut TouchGroup, slot: i32, x: i32, y: i32, major: Option<i32>) {
+        group.touch(Some(slot), 0);
+        let contact = group.slots.get_mut(&Some(slot)).unwrap();
+        contact.pos.x(x, Duration::ZERO);
+        contact.pos.y(y, Duration::ZERO);
+        if let Some(m) = major {
+            contact.pos.major(m);
+        }
+    }
+
+    #[test]
+    fn rejects_until_min_lifetime() {
+        let mut filter = NoiseFilter::new();
+        let mut group = TouchGroup::new();
+        place(&mut group, 0, 10, 10, None);
+        let config = cfg(2, 0, 2, 5);
+
+        filter.observe(&group, &config);
+        assert_eq!(filter.status(), TouchStatus::None); // not trusted on the first frame
+        filter.observe(&group, &config);
+        assert_eq!(filter.status(), TouchStatus::Click); // accepted on the second
+    }
+
+    #[test]
+    fn rejects_palm_above_threshold() {
+        let mut filter = NoiseFilter::new();
+        let mut group = TouchGroup::new();
+        place(&mut group, 0, 10, 10, Some(50));
+
+        filter.observe(&group, &cfg(1, 20, 2, 5));
+        assert_eq!(filter.status(), TouchStatus::None);
+    }
+
+    #[test]
+    fn monotone_movement_becomes_slide() {
+        let mut filter = NoiseFilter::new();
+        let mut group = TouchGroup::new();
+        let config = cfg(1, 0, 2, 5);
+
+        for (frame, x) in [0, 20, 40].into_iter().enumerate() {
+            place(&mut group, 0, x, 0, None);
+            filter.observe(&group, &config);
+            if frame < 2 {
+                assert_ne!(filter.status(), TouchStatus::Slide);
+            }
+        }
+
+        assert_eq!(filter.status(), TouchStatus::Slide);
+    }
+}
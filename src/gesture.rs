@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use super::touch_group::TouchGroup;
+
+/// Direction of a [`Gesture::Swipe`], chosen by the dominant axis of the
+/// accumulated displacement
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A recognized multi-finger gesture
+///
+/// Emitted per sync frame by [`GestureClassifier`] and surfaced to consumers
+/// through [`TouchListener::gestures`].
+///
+/// [`TouchListener::gestures`]: crate::TouchListener::gestures
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// `count` fingers touched down and lifted without a swipe or pinch being
+    /// recognized (movement below both thresholds still ends in a tap)
+    Tap { count: usize },
+    /// `fingers` contacts slid past the threshold along `dir`
+    Swipe { dir: Direction, fingers: usize },
+    /// Two or more contacts changed their mean pairwise distance by `scale`
+    /// (relative to the distance when the pinch began)
+    Pinch { scale: f64 },
+}
+
+/// Classifies a stream of [`TouchGroup`] frames into [`Gesture`]s
+///
+/// State machine over one touch sequence (first contact down until the last
+/// one lifts): tracks the peak contact count, each contact's touch-down
+/// position and the initial pinch distance, then emits a swipe/pinch as soon
+/// as its threshold is crossed and a tap on release when nothing else fired.
+#[derive(Debug, Default)]
+pub struct GestureClassifier {
+    start_pos: HashMap<Option<i32>, (i32, i32)>,
+    max_fingers: usize,
+    initial_pinch: Option<f64>,
+    fired: bool, // a swipe or pinch already fired this sequence
+}
+
+impl GestureClassifier {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one sync frame
+    ///
+    /// `swipe_pixel` is the summed-displacement magnitude (in pixels) above
+    /// which a slide becomes a swipe; `pinch_percent` is the percentage change
+    /// in mean pairwise distance above which a pinch is reported. Returns the
+    /// gestures recognized on this frame (usually none).
+    pub fn update(
+        &mut self,
+        group: &TouchGroup,
+        swipe_pixel: usize,
+        pinch_percent: usize,
+    ) -> Vec<Gesture> {
+        if group.is_empty() {
+            return self.finish();
+        }
+
+        let count = group.len();
+
+        self.max_fingers = self.max_fingers.max(count);
+
+        // remember the touch-down position of every newly seen slot
+        for (slot, contact) in &group.slots {
+            if let (Some(x), Some(y)) = contact.pos.cur_pos {
+                self.start_pos.entry(*slot).or_insert((x, y));
+            }
+        }
+
+        let mut gestures = Vec::new();
+
+        if !self.fired {
+            if let Some(dir) = self.swipe(group, swipe_pixel) {
+                self.fired = true;
+                gestures.push(Gesture::Swipe {
+                    dir,
+                    fingers: count,
+                });
+            } else if let Some(scale) = self.pinch(group, pinch_percent) {
+                self.fired = true;
+                gestures.push(Gesture::Pinch { scale });
+            }
+        }
+
+        gestures
+    }
+
+    /// Summed displacement since touch-down, classified by dominant axis
+    fn swipe(&self, group: &TouchGroup, swipe_pixel: usize) -> Option<Direction> {
+        let (mut dx, mut dy) = (0i64, 0i64);
+
+        for (slot, contact) in &group.slots {
+            let (Some(x), Some(y)) = contact.pos.cur_pos else {
+                continue;
+            };
+            if let Some((sx, sy)) = self.start_pos.get(slot) {
+                dx += i64::from(x - sx);
+                dy += i64::from(y - sy);
+            }
+        }
+
+        let mag = ((dx * dx + dy * dy) as f64).sqrt();
+        if mag.round() as usize <= swipe_pixel {
+            return None;
+        }
+
+        Some(if dx.abs() >= dy.abs() {
+            if dx >= 0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
+        } else if dy >= 0 {
+            Direction::Down
+        } else {
+            Direction::Up
+        })
+    }
+
+    /// Relative change in mean pairwise distance between two or more contacts
+    fn pinch(&mut self, group: &TouchGroup, pinch_percent: usize) -> Option<f64> {
+        let points: Vec<(i32, i32)> = group
+            .slots
+            .values()
+            .filter_map(|c| match c.pos.cur_pos {
+                (Some(x), Some(y)) => Some((x, y)),
+                _ => None,
+            })
+            .collect();
+
+        if points.len() < 2 {
+            return None;
+        }
+
+        let mut sum = 0.0;
+        let mut pairs = 0u32;
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                let dx = f64::from(points[i].0 - points[j].0);
+                let dy = f64::from(points[i].1 - points[j].1);
+                sum += (dx * dx + dy * dy).sqrt();
+                pairs += 1;
+            }
+        }
+        let mean = sum / f64::from(pairs);
+
+        let initial = *self.initial_pinch.get_or_insert(mean);
+        if initial <= f64::EPSILON {
+            return None;
+        }
+
+        let scale = mean / initial;
+        if (scale - 1.0).abs() * 100.0 > pinch_percent as f64 {
+            Some(scale)
+        } else {
+            None
+        }
+    }
+
+    /// The last contact lifted: emit a tap when no swipe/pinch fired, then reset
+    fn finish(&mut self) -> Vec<Gesture> {
+        let gestures = if self.max_fingers > 0 && !self.fired {
+            vec![Gesture::Tap {
+                count: self.max_fingers,
+            }]
+        } else {
+            Vec::new()
+        };
+
+        *self = Self::default();
+        gestures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Gesture, GestureClassifier};
+    use crate::touch_group::TouchGroup;
+    use std::time::Duration;
+
+    fn place(group: &mut TouchGroup, slot: i32, x: i32, y: i32) {
+        group.touch(Some(slot), 0);
+        let contact = group.slots.get_mut(&Some(slot)).unwrap();
+        contact.pos.x(x, Duration::ZERO);
+        contact.pos.y(y, Duration::ZERO);
+    }
+
+    #[test]
+    fn tap_on_release() {
+        let mut classifier = GestureClassifier::new();
+        let mut group = TouchGroup::new();
+        place(&mut group, 0, 5, 5);
+
+        assert!(classifier.update(&group, 30, 20).is_empty());
+        let released = classifier.update(&TouchGroup::new(), 30, 20);
+        assert_eq!(released, vec![Gesture::Tap { count: 1 }]);
+    }
+
+    #[test]
+    fn swipe_right_past_threshold() {
+        let mut classifier = GestureClassifier::new();
+        let mut down = TouchGroup::new();
+        place(&mut down, 0, 0, 0);
+        assert!(classifier.update(&down, 30, 20).is_empty());
+
+        let mut moved = TouchGroup::new();
+        place(&mut moved, 0, 100, 0);
+        assert_eq!(
+            classifier.update(&moved, 30, 20),
+            vec![Gesture::Swipe {
+                dir: Direction::Right,
+                fingers: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn pinch_out_past_threshold() {
+        let mut classifier = GestureClassifier::new();
+        let mut down = TouchGroup::new();
+        place(&mut down, 0, 0, 0);
+        place(&mut down, 1, 10, 0);
+        // large swipe threshold so only the pinch can fire
+        assert!(classifier.update(&down, 10_000, 20).is_empty());
+
+        let mut spread = TouchGroup::new();
+        place(&mut spread, 0, 0, 0);
+        place(&mut spread, 1, 30, 0);
+        let out = classifier.update(&spread, 10_000, 20);
+
+        assert_eq!(out.len(), 1);
+        assert!(matches!(out[0], Gesture::Pinch { scale } if (scale - 3.0).abs() < 1e-6));
+    }
+}
@@ -0,0 +1,186 @@
+use std::{io, thread, time::Duration};
+
+use evdev::{
+    uinput::{VirtualDevice, VirtualDeviceBuilder},
+    AbsInfo, AbsoluteAxisType, EventType, InputEvent, Synchronization, UinputAbsSetup,
+};
+
+/// Number of interpolation steps emitted per second by [`VirtualTouchscreen::swipe`]
+const SWIPE_HZ: u64 = 60;
+
+/// A single contact for [`VirtualTouchscreen::multi_touch`]
+#[derive(Debug, Clone, Copy)]
+pub struct Contact {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A virtual multitouch device created through `/dev/uinput`
+///
+/// Mirrors the Fuchsia input-synthesis `InputDeviceRegistry`/`InputDevice`
+/// idea: advertise the MT slot/tracking-id/position axes once, then feed the
+/// kernel the `ABS_MT_SLOT` / `ABS_MT_TRACKING_ID` / `ABS_MT_POSITION_X` /
+/// `ABS_MT_POSITION_Y` / `SYN_REPORT` sequences a real panel would, so
+/// [`daemon_thread`] and friends can be exercised on machines with no
+/// touchscreen.
+///
+/// [`daemon_thread`]: crate::read
+#[derive(Debug)]
+pub struct VirtualTouchscreen {
+    device: VirtualDevice,
+    next_id: i32,
+}
+
+impl VirtualTouchscreen {
+    /// Create a virtual touchscreen spanning `0..=max_x` by `0..=max_y`
+    ///
+    /// # Errors
+    ///
+    /// Opening or configuring `/dev/uinput` failed (often a permission issue)
+    pub fn new(max_x: i32, max_y: i32) -> io::Result<Self> {
+        let slot = UinputAbsSetup::new(
+            AbsoluteAxisType::ABS_MT_SLOT,
+            AbsInfo::new(0, 0, 9, 0, 0, 0),
+        );
+        let id = UinputAbsSetup::new(
+            AbsoluteAxisType::ABS_MT_TRACKING_ID,
+            AbsInfo::new(0, 0, i32::MAX, 0, 0, 0),
+        );
+        let x = UinputAbsSetup::new(
+            AbsoluteAxisType::ABS_MT_POSITION_X,
+            AbsInfo::new(0, 0, max_x, 0, 0, 0),
+        );
+        let y = UinputAbsSetup::new(
+            AbsoluteAxisType::ABS_MT_POSITION_Y,
+            AbsInfo::new(0, 0, max_y, 0, 0, 0),
+        );
+
+        let device = VirtualDeviceBuilder::new()?
+            .name("touch_event virtual touchscreen")
+            .with_absolute_axis(&slot)?
+            .with_absolute_axis(&id)?
+            .with_absolute_axis(&x)?
+            .with_absolute_axis(&y)?
+            .build()?;
+
+        Ok(Self { device, next_id: 0 })
+    }
+
+    /// Briefly touch down and lift at `(x, y)`
+    ///
+    /// # Errors
+    ///
+    /// Writing to the virtual device failed
+    pub fn tap(&mut self, x: i32, y: i32) -> io::Result<()> {
+        self.multi_touch(&[Contact { x, y }])
+    }
+
+    /// Drag a single contact from `from` to `to` over `duration`
+    ///
+    /// # Errors
+    ///
+    /// Writing to the virtual device failed
+    pub fn swipe(&mut self, from: (i32, i32), to: (i32, i32), duration: Duration) -> io::Result<()> {
+        // round up so even sub-frame durations traverse at least from -> to
+        let steps = (duration.as_millis() as u64 * SWIPE_HZ).div_ceil(1000).max(1);
+        let id = self.take_id();
+
+        // touch down at `from` in its own frame, otherwise the buffered position
+        // is overwritten by the first interpolated step before the kernel syncs
+        let mut down_frame = down(0, id, from.0, from.1).to_vec();
+        down_frame.push(syn());
+        self.emit(&down_frame)?;
+
+        for step in 1..=steps {
+            let t = step as f64 / steps as f64;
+            let x = lerp(from.0, to.0, t);
+            let y = lerp(from.1, to.1, t);
+            self.emit(&[
+                InputEvent::new(EventType::ABSOLUTE, AbsoluteAxisType::ABS_MT_SLOT.0, 0),
+                InputEvent::new(EventType::ABSOLUTE, AbsoluteAxisType::ABS_MT_POSITION_X.0, x),
+                InputEvent::new(EventType::ABSOLUTE, AbsoluteAxisType::ABS_MT_POSITION_Y.0, y),
+                syn(),
+            ])?;
+            thread::sleep(duration / steps as u32);
+        }
+
+        self.emit(&up(0))
+    }
+
+    /// Tap several contacts down simultaneously and lift them together
+    ///
+    /// # Errors
+    ///
+    /// Writing to the virtual device failed
+    pub fn multi_touch(&mut self, contacts: &[Contact]) -> io::Result<()> {
+        let mut down_frame = Vec::new();
+        for (slot, contact) in contacts.iter().enumerate() {
+            let id = self.take_id();
+            down_frame.extend(down(slot as i32, id, contact.x, contact.y));
+        }
+        down_frame.push(syn());
+        self.emit(&down_frame)?;
+
+        // hold for a second sync frame so the contacts survive any non-trivial
+        // `min_lifetime` filter before they are lifted
+        self.emit(&[syn()])?;
+
+        let mut up_frame = Vec::new();
+        for slot in 0..contacts.len() {
+            // drop each lift's own SYN_REPORT so every contact lifts in one frame
+            up_frame.extend_from_slice(&up(slot as i32)[..2]);
+        }
+        up_frame.push(syn());
+        self.emit(&up_frame)
+    }
+
+    /// Allocate a fresh tracking id
+    fn take_id(&mut self) -> i32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1).max(0);
+        id
+    }
+
+    fn emit(&mut self, events: &[InputEvent]) -> io::Result<()> {
+        self.device.emit(events)
+    }
+}
+
+/// Select `slot`, assign `id` and place the contact at `(x, y)`
+fn down(slot: i32, id: i32, x: i32, y: i32) -> [InputEvent; 4] {
+    [
+        InputEvent::new(EventType::ABSOLUTE, AbsoluteAxisType::ABS_MT_SLOT.0, slot),
+        InputEvent::new(
+            EventType::ABSOLUTE,
+            AbsoluteAxisType::ABS_MT_TRACKING_ID.0,
+            id,
+        ),
+        InputEvent::new(EventType::ABSOLUTE, AbsoluteAxisType::ABS_MT_POSITION_X.0, x),
+        InputEvent::new(EventType::ABSOLUTE, AbsoluteAxisType::ABS_MT_POSITION_Y.0, y),
+    ]
+}
+
+/// Lift the contact on `slot` (tracking id = -1), followed by a `SYN_REPORT`
+fn up(slot: i32) -> [InputEvent; 3] {
+    [
+        InputEvent::new(EventType::ABSOLUTE, AbsoluteAxisType::ABS_MT_SLOT.0, slot),
+        InputEvent::new(
+            EventType::ABSOLUTE,
+            AbsoluteAxisType::ABS_MT_TRACKING_ID.0,
+            -1,
+        ),
+        syn(),
+    ]
+}
+
+fn syn() -> InputEvent {
+    InputEvent::new(
+        EventType::SYNCHRONIZATION,
+        Synchronization::SYN_REPORT.0,
+        0,
+    )
+}
+
+fn lerp(a: i32, b: i32, t: f64) -> i32 {
+    (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as i32
+}
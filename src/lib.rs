@@ -1,6 +1,11 @@
 pub(crate) mod analyze;
+pub mod filter;
+pub mod gesture;
+pub mod inject;
 mod read;
+pub mod resample;
 pub(crate) mod touch_group;
+mod watch;
 
 use std::{
     collections::HashMap,
@@ -10,15 +15,35 @@ use std::{
     sync::{
         atomic::AtomicUsize,
         mpsc::{self, Receiver},
-        Arc,
+        Arc, Mutex, RwLock,
     },
     thread,
     time::Duration,
 };
 
-use atomic::{Atomic, Ordering};
+use atomic::Ordering;
 use evdev::{Device, EventType};
 
+use gesture::Gesture;
+use resample::{ContactSample, Resampler};
+use watch::Shared;
+
+/// Default summed-displacement magnitude (px) above which a slide is a swipe
+const DEFAULT_SWIPE_PIXEL: usize = 30;
+
+/// Default change (percent) in mean pairwise distance that reports a pinch
+const DEFAULT_PINCH_PERCENT: usize = 20;
+
+/// Default contact lifetime (sync frames) required before it is trusted
+/// (`1` = accept immediately; raise it to opt into transient-noise rejection)
+const DEFAULT_MIN_LIFETIME: usize = 1;
+
+/// Default palm size threshold (`0` disables palm rejection)
+const DEFAULT_PALM_MAJOR: usize = 0;
+
+/// Default monotone frames required before a slide is declared
+const DEFAULT_DEBOUNCE: usize = 2;
+
 /// Listen for touch events
 ///
 /// Implemented[`std::ops::Deref`]to access internal`status_map`
@@ -32,8 +57,8 @@ use evdev::{Device, EventType};
 /// let listener = TouchListener::new(5).unwrap();
 /// thread::sleep(Duration::from_secs(1)); // Just listen for a while
 ///
-/// // Deref to HashMap inside it
-/// for atom_status in listener.values() {
+/// // Deref to the RwLock-guarded HashMap inside it
+/// for atom_status in listener.read().unwrap().values() {
 ///     let status = atom_status.load(Ordering::Acquire);
 ///     println!("{status:?}");
 /// }
@@ -43,12 +68,41 @@ use evdev::{Device, EventType};
 
 #[derive(Debug)]
 pub struct TouchListener {
-    status_map: HashMap<usize, Arc<AtomicTouchStatus>>,
+    status_map: Arc<RwLock<StatusMap>>,
+    samplers: Arc<RwLock<SamplerMap>>,
+    contacts: Arc<RwLock<ContactsMap>>,
     wait: Receiver<()>,
+    gestures: Receiver<Gesture>,
     min_pixel: Arc<AtomicUsize>,
+    swipe_pixel: Arc<AtomicUsize>,
+    pinch_percent: Arc<AtomicUsize>,
+    min_lifetime: Arc<AtomicUsize>,
+    palm_major: Arc<AtomicUsize>,
+    debounce: Arc<AtomicUsize>,
 }
 
-pub(crate) type AtomicTouchStatus = Atomic<TouchStatus>;
+pub(crate) type AtomicTouchStatus = atomic::Atomic<TouchStatus>;
+
+/// Device id (`eventN`) to its shared touch status
+pub(crate) type StatusMap = HashMap<usize, Arc<AtomicTouchStatus>>;
+
+/// Device id (`eventN`) to its shared [`Resampler`]
+pub(crate) type SamplerMap = HashMap<usize, Arc<Mutex<Resampler>>>;
+
+/// Device id (`eventN`) to its latest per-slot contact snapshot
+pub(crate) type ContactsMap = HashMap<usize, Arc<RwLock<Vec<ContactSnapshot>>>>;
+
+/// A live contact as seen at the last sync frame
+///
+/// Keyed by `slot` (the evdev MT-B identity), carrying its tracking `id` and
+/// current `(x, y)` position so consumers can do their own geometry instead of
+/// only reading the aggregate tri-state [`TouchStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContactSnapshot {
+    pub slot: Option<i32>,
+    pub id: Option<i32>,
+    pub pos: (Option<i32>, Option<i32>),
+}
 
 /// Indicates the current touch state
 ///
@@ -65,7 +119,7 @@ pub enum TouchStatus {
 }
 
 impl Deref for TouchListener {
-    type Target = HashMap<usize, Arc<AtomicTouchStatus>>;
+    type Target = RwLock<StatusMap>;
 
     fn deref(&self) -> &Self::Target {
         &self.status_map
@@ -89,11 +143,7 @@ impl TouchListener {
                 let path = f.path();
                 let device = Device::open(path).ok()?;
 
-                let event_len = "event".len();
-                let id: usize = f.file_name().into_string().ok()?[event_len..]
-                    .trim()
-                    .parse()
-                    .ok()?;
+                let id = watch::parse_event_id(&f.file_name().into_string().ok()?)?;
 
                 Some((id, device))
             })
@@ -104,31 +154,54 @@ impl TouchListener {
             return Err("No usable touch device".into());
         }
 
-        let mut status_map = HashMap::new();
+        let status_map = Arc::new(RwLock::new(StatusMap::new()));
+        let samplers = Arc::new(RwLock::new(SamplerMap::new()));
+        let contacts = Arc::new(RwLock::new(ContactsMap::new()));
         let (sx, rx) = mpsc::sync_channel(1);
-        let min_pixel = Arc::new(AtomicUsize::new(min_pixel));
+        let (gesture_sx, gesture_rx) = mpsc::channel();
 
-        for (id, device) in devices {
-            let touch_status = Arc::new(Atomic::new(TouchStatus::None));
-            let touch_status_clone = touch_status.clone();
-            let sx = sx.clone();
-            let min_pixel = min_pixel.clone();
+        let shared = Shared {
+            gestures: gesture_sx,
+            min_pixel: Arc::new(AtomicUsize::new(min_pixel)),
+            swipe_pixel: Arc::new(AtomicUsize::new(DEFAULT_SWIPE_PIXEL)),
+            pinch_percent: Arc::new(AtomicUsize::new(DEFAULT_PINCH_PERCENT)),
+            min_lifetime: Arc::new(AtomicUsize::new(DEFAULT_MIN_LIFETIME)),
+            palm_major: Arc::new(AtomicUsize::new(DEFAULT_PALM_MAJOR)),
+            debounce: Arc::new(AtomicUsize::new(DEFAULT_DEBOUNCE)),
+        };
 
-            status_map.insert(id, touch_status);
-
-            thread::Builder::new()
-                .name("TouchDeviceListener".into())
-                .spawn(move || read::daemon_thread(device, &touch_status_clone, &sx, &min_pixel))?;
+        for (id, device) in devices {
+            watch::spawn_device(id, device, &status_map, &samplers, &contacts, &sx, &shared)?;
         }
 
-        if status_map.is_empty() {
+        if status_map.read().unwrap().is_empty() {
             return Err("No usable touch device".into());
         }
 
+        // Watch `/dev/input` so devices plugged in after construction are picked up
+        {
+            let status_map = status_map.clone();
+            let samplers = samplers.clone();
+            let contacts = contacts.clone();
+            let sx = sx.clone();
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name("TouchDeviceWatcher".into())
+                .spawn(move || watch::watcher(status_map, samplers, contacts, sx, shared))?;
+        }
+
         Ok(Self {
             status_map,
+            samplers,
+            contacts,
             wait: rx,
-            min_pixel,
+            gestures: gesture_rx,
+            min_pixel: shared.min_pixel,
+            swipe_pixel: shared.swipe_pixel,
+            pinch_percent: shared.pinch_percent,
+            min_lifetime: shared.min_lifetime,
+            palm_major: shared.palm_major,
+            debounce: shared.debounce,
         })
     }
 
@@ -137,6 +210,45 @@ impl TouchListener {
         self.min_pixel.store(p, Ordering::Release);
     }
 
+    /// Set the summed-displacement magnitude (px) above which a slide is
+    /// classified as a [`gesture::Gesture::Swipe`]
+    pub fn swipe_pixel(&self, p: usize) {
+        self.swipe_pixel.store(p, Ordering::Release);
+    }
+
+    /// Set the change (percent) in mean pairwise distance that reports a
+    /// [`gesture::Gesture::Pinch`]
+    pub fn pinch_percent(&self, p: usize) {
+        self.pinch_percent.store(p, Ordering::Release);
+    }
+
+    /// Set the contact lifetime, in sync frames, required before a contact is
+    /// trusted (shorter-lived contacts are rejected as transient noise)
+    pub fn min_lifetime(&self, frames: usize) {
+        self.min_lifetime.store(frames, Ordering::Release);
+    }
+
+    /// Set the palm size threshold on `ABS_MT_TOUCH_MAJOR`/`ABS_MT_PRESSURE`
+    /// above which a contact is dropped (`0` disables palm rejection)
+    pub fn palm_major(&self, major: usize) {
+        self.palm_major.store(major, Ordering::Release);
+    }
+
+    /// Set the number of consecutive monotone frames required before a slide
+    /// is declared, debouncing tiny oscillating movements
+    pub fn debounce(&self, frames: usize) {
+        self.debounce.store(frames, Ordering::Release);
+    }
+
+    /// Receiver of recognized multi-finger [`gesture::Gesture`]s
+    ///
+    /// Gestures from every device are merged onto a single channel; call
+    /// [`std::sync::mpsc::Receiver::try_recv`] to drain them without blocking.
+    #[must_use]
+    pub fn gestures(&self) -> &Receiver<Gesture> {
+        &self.gestures
+    }
+
     /// Block and waiting for touch status to update
     ///
     /// # Errors
@@ -165,19 +277,53 @@ impl TouchListener {
     ///
     /// If at least one device is in the corresponding state, then the corresponding state is true
     pub fn status(&self) -> (bool, bool, bool) {
-        let slide = self
-            .status_map
+        let map = self.status_map.read().unwrap();
+
+        let slide = map
             .values()
             .any(|s| s.load(Ordering::Acquire) == TouchStatus::Slide);
-        let click = self
-            .status_map
+        let click = map
             .values()
             .any(|s| s.load(Ordering::Acquire) == TouchStatus::Click);
-        let none = self
-            .status_map
+        let none = map
             .values()
             .any(|s| s.load(Ordering::Acquire) == TouchStatus::None);
 
         (slide, click, none)
     }
+
+    /// Snapshot of the live contacts on every device
+    ///
+    /// Each entry maps a device id (`eventN`) to the set of [`ContactSnapshot`]s
+    /// present at that device's last sync frame, so consumers can read real
+    /// per-slot geometry (slot, tracking id, position) instead of only the
+    /// aggregate tri-state from [`status`].
+    ///
+    /// [`status`]: TouchListener::status
+    #[must_use]
+    pub fn contacts(&self) -> HashMap<usize, Vec<ContactSnapshot>> {
+        self.contacts
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, contacts)| (*id, contacts.read().unwrap().clone()))
+            .collect()
+    }
+
+    /// Resample every device's live contacts at `sample_time`
+    ///
+    /// `sample_time` is a [`Duration`] on the same clock as the evdev event
+    /// timestamps (i.e. since the `UNIX_EPOCH`). For each device the returned
+    /// vec holds one [`ContactSample`] per live contact, with an interpolated
+    /// position and a velocity in pixels per second, so an animation loop can
+    /// sample contacts at its own frame cadence instead of the raw report rate.
+    #[must_use]
+    pub fn resample(&self, sample_time: Duration) -> HashMap<usize, Vec<ContactSample>> {
+        self.samplers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, resampler)| (*id, resampler.lock().unwrap().sample(sample_time)))
+            .collect()
+    }
 }
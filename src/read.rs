@@ -1,22 +1,52 @@
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    mpsc::SyncSender,
-    Arc,
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{Sender, SyncSender},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, UNIX_EPOCH},
 };
 
 use evdev::{AbsoluteAxisType, Device, EventType, InputEventKind};
 
 use super::{
     analyze::analyze,
-    touch_group::{TouchGroup, TouchPos},
-    AtomicTouchStatus,
+    filter::{FilterConfig, NoiseFilter},
+    gesture::{Gesture, GestureClassifier},
+    resample::Resampler,
+    touch_group::TouchGroup,
+    AtomicTouchStatus, ContactSnapshot,
 };
 
+/// Atomic thresholds the filtering stage reads afresh every sync frame
+pub struct FilterAtomics<'a> {
+    pub min_pixel: &'a Arc<AtomicUsize>,
+    pub min_lifetime: &'a Arc<AtomicUsize>,
+    pub palm_major: &'a Arc<AtomicUsize>,
+    pub debounce: &'a Arc<AtomicUsize>,
+}
+
+impl FilterAtomics<'_> {
+    fn resolve(&self) -> FilterConfig {
+        FilterConfig {
+            min_pixel: self.min_pixel.load(Ordering::Acquire),
+            min_lifetime: self.min_lifetime.load(Ordering::Acquire),
+            palm_major: self.palm_major.load(Ordering::Acquire),
+            debounce: self.debounce.load(Ordering::Acquire),
+        }
+    }
+}
+
 pub fn daemon_thread(
     mut touch_device: Device,
     status: &Arc<AtomicTouchStatus>,
     notice: &SyncSender<()>,
-    min_pixel: &Arc<AtomicUsize>,
+    resampler: &Arc<Mutex<Resampler>>,
+    gestures: &Sender<Gesture>,
+    swipe_pixel: &Arc<AtomicUsize>,
+    pinch_percent: &Arc<AtomicUsize>,
+    filter_cfg: &FilterAtomics,
+    contacts: &Arc<RwLock<Vec<ContactSnapshot>>>,
 ) {
     if !touch_device
         .supported_events()
@@ -29,49 +59,74 @@ pub fn daemon_thread(
     let mut group = TouchGroup::new();
     let mut target = (None, None); // id, slot
     let mut cache = Vec::new();
+    let mut time = Duration::default(); // timestamp of the frame being decoded
+    let mut classifier = GestureClassifier::new();
+    let mut filter = NoiseFilter::new();
 
     loop {
-        let events = touch_device.fetch_events().unwrap();
+        let Ok(events) = touch_device.fetch_events() else {
+            // ENODEV: the device was unplugged, let the thread unwind so the
+            // watcher's wrapper can drop its `status_map` entry
+            return;
+        };
 
         for event in events {
+            time = event
+                .timestamp()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+
             if let InputEventKind::AbsAxis(abs) = event.kind() {
                 match abs {
                     AbsoluteAxisType::ABS_MT_TRACKING_ID => {
-                        update_group(
-                            &mut group,
-                            &mut target,
-                            &mut cache,
-                            status,
-                            notice,
-                            min_pixel,
-                        );
+                        update_group(&mut group, &mut target, &mut cache, time);
                         target.0 = Some(event.value());
                     }
                     AbsoluteAxisType::ABS_MT_SLOT => {
-                        update_group(
-                            &mut group,
-                            &mut target,
-                            &mut cache,
-                            status,
-                            notice,
-                            min_pixel,
-                        );
+                        update_group(&mut group, &mut target, &mut cache, time);
                         target.1 = Some(event.value());
                     }
-                    AbsoluteAxisType::ABS_MT_POSITION_X | AbsoluteAxisType::ABS_MT_POSITION_Y => {
+                    AbsoluteAxisType::ABS_MT_POSITION_X
+                    | AbsoluteAxisType::ABS_MT_POSITION_Y
+                    | AbsoluteAxisType::ABS_MT_TOUCH_MAJOR
+                    | AbsoluteAxisType::ABS_MT_PRESSURE => {
                         cache.push((abs, event.value()));
                     }
                     _ => (),
                 }
             } else if let InputEventKind::Synchronization(_) = event.kind() {
-                update_group(
-                    &mut group,
-                    &mut target,
-                    &mut cache,
-                    status,
-                    notice,
-                    min_pixel,
-                );
+                update_group(&mut group, &mut target, &mut cache, time);
+
+                // filtering stage: reject noise/palm/jitter, then analyze
+                filter.observe(&group, &filter_cfg.resolve());
+                analyze(status, notice, &filter);
+
+                // publish this frame's samples for TouchListener::resample
+                if let Ok(mut resampler) = resampler.lock() {
+                    resampler.update(&group);
+                }
+
+                // publish a per-slot contact snapshot for TouchListener::contacts
+                if let Ok(mut contacts) = contacts.write() {
+                    *contacts = group
+                        .slots
+                        .iter()
+                        .map(|(slot, contact)| ContactSnapshot {
+                            slot: *slot,
+                            id: contact.id,
+                            pos: contact.pos.cur_pos,
+                        })
+                        .collect();
+                }
+
+                // classify multi-finger gestures for TouchListener::gestures
+                for gesture in classifier.update(
+                    &group,
+                    swipe_pixel.load(Ordering::Acquire),
+                    pinch_percent.load(Ordering::Acquire),
+                ) {
+                    let _ = gestures.send(gesture);
+                }
             }
         }
     }
@@ -81,43 +136,33 @@ fn update_group(
     group: &mut TouchGroup,
     target: &mut (Option<i32>, Option<i32>),
     events: &mut Vec<(AbsoluteAxisType, i32)>,
-    status: &Arc<AtomicTouchStatus>,
-    notice: &SyncSender<()>,
-    min_pixel: &Arc<AtomicUsize>,
+    time: Duration,
 ) {
     if events.is_empty() && target.0.is_none() {
         return;
     } // 如果没有事件，也没有更新/删除id的目标，那么就没有任何事要做
 
     if let Some(id) = target.0 {
-        use std::collections::hash_map::Entry;
-
         if id == -1 {
-            group.remove_id();
+            group.lift(target.1);
             target.0 = None;
-            analyze(group, status, notice, min_pixel.load(Ordering::Acquire));
             return;
         }
 
-        if let Entry::Vacant(e) = group.id_slot.entry(id) {
-            e.insert(target.1);
-            group.slot_pos.insert(target.1, TouchPos::new());
-        }
+        group.touch(target.1, id);
     }
 
-    analyze(group, status, notice, min_pixel.load(Ordering::Acquire));
-
     for (t, v) in &*events {
-        analyze(group, status, notice, min_pixel.load(Ordering::Acquire));
-
-        let Some(pos) = group.slot_pos.get_mut(&target.1) else {
+        let Some(contact) = group.slots.get_mut(&target.1) else {
             *target = (None, None);
             return;
         };
 
         match *t {
-            AbsoluteAxisType::ABS_MT_POSITION_X => pos.x(*v),
-            AbsoluteAxisType::ABS_MT_POSITION_Y => pos.y(*v),
+            AbsoluteAxisType::ABS_MT_POSITION_X => contact.pos.x(*v, time),
+            AbsoluteAxisType::ABS_MT_POSITION_Y => contact.pos.y(*v, time),
+            AbsoluteAxisType::ABS_MT_TOUCH_MAJOR => contact.pos.major(*v),
+            AbsoluteAxisType::ABS_MT_PRESSURE => contact.pos.pressure(*v),
             _ => (),
         }
     }
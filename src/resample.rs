@@ -0,0 +1,164 @@
+use std::{collections::HashMap, time::Duration};
+
+use super::touch_group::TouchGroup;
+
+/// A single `(time, x, y)` position sample for one slot
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    time: Duration,
+    x: f64,
+    y: f64,
+}
+
+/// An interpolated contact produced by [`Resampler::sample`]
+///
+/// `pos` is the position at the requested sample time; `velocity` is the
+/// instantaneous speed of the contact in pixels per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactSample {
+    pub slot: Option<i32>,
+    pub pos: (f64, f64),
+    pub velocity: (f64, f64),
+}
+
+/// Keeps the last two samples of every active slot so downstream animation
+/// loops can sample contacts at their own cadence instead of the raw evdev
+/// report rate
+///
+/// Modeled on carnelian's `TouchEventResampler`: interpolation is linear
+/// between the last two samples and clamped to the available range.
+#[derive(Debug, Default)]
+pub struct Resampler {
+    slots: HashMap<Option<i32>, [Option<Sample>; 2]>, // [last, next]
+}
+
+impl Resampler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            slots: HashMap::new(),
+        }
+    }
+
+    /// Ingest the current frame of a [`TouchGroup`]
+    ///
+    /// For every live slot whose current position carries a fresher timestamp
+    /// than the one already stored, the pair is shifted (`next` becomes `last`)
+    /// and the new sample becomes `next`. Slots that are no longer present are
+    /// dropped.
+    pub fn update(&mut self, group: &TouchGroup) {
+        self.slots.retain(|slot, _| group.slots.contains_key(slot));
+
+        for (slot, contact) in &group.slots {
+            let pos = &contact.pos;
+            let (Some(x), Some(y), Some(time)) = (pos.cur_pos.0, pos.cur_pos.1, pos.cur_time) else {
+                continue;
+            };
+
+            let sample = Sample {
+                time,
+                x: f64::from(x),
+                y: f64::from(y),
+            };
+
+            let pair = self.slots.entry(*slot).or_default();
+            if pair[1].is_some_and(|s| s.time == time) {
+                continue; // nothing new this frame
+            }
+
+            pair[0] = pair[1];
+            pair[1] = Some(sample);
+        }
+    }
+
+    /// Interpolate every contact to `sample_time`
+    ///
+    /// pos = last + (next - last) * (sample_time - last_t) / (next_t - last_t),
+    /// clamped to `[last, next]`; velocity = (next - last) / (next_t - last_t).
+    #[must_use]
+    pub fn sample(&self, sample_time: Duration) -> Vec<ContactSample> {
+        self.slots
+            .iter()
+            .filter_map(|(slot, pair)| {
+                let interp = match *pair {
+                    [Some(last), Some(next)] => interpolate(last, next, sample_time),
+                    [None, Some(only)] | [Some(only), None] => ContactSample {
+                        slot: *slot,
+                        pos: (only.x, only.y),
+                        velocity: (0.0, 0.0),
+                    },
+                    [None, None] => return None,
+                };
+
+                Some(ContactSample {
+                    slot: *slot,
+                    ..interp
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Resampler;
+    use crate::touch_group::TouchGroup;
+    use std::time::Duration;
+
+    fn set(group: &mut TouchGroup, slot: i32, x: i32, y: i32, t: Duration) {
+        group.touch(Some(slot), 0);
+        let contact = group.slots.get_mut(&Some(slot)).unwrap();
+        contact.pos.x(x, t);
+        contact.pos.y(y, t);
+    }
+
+    #[test]
+    fn interpolates_position_and_velocity() {
+        let mut resampler = Resampler::new();
+        let mut group = TouchGroup::new();
+
+        set(&mut group, 0, 0, 0, Duration::from_secs(0));
+        resampler.update(&group);
+        set(&mut group, 0, 100, 0, Duration::from_secs(1));
+        resampler.update(&group);
+
+        let sampled = resampler.sample(Duration::from_millis(500));
+        assert_eq!(sampled.len(), 1);
+        assert_eq!(sampled[0].slot, Some(0));
+        assert!((sampled[0].pos.0 - 50.0).abs() < 1e-6);
+        assert!((sampled[0].velocity.0 - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clamps_past_last_sample_and_drops_lifted() {
+        let mut resampler = Resampler::new();
+        let mut group = TouchGroup::new();
+
+        set(&mut group, 0, 0, 0, Duration::from_secs(0));
+        resampler.update(&group);
+        set(&mut group, 0, 100, 0, Duration::from_secs(1));
+        resampler.update(&group);
+
+        let late = resampler.sample(Duration::from_secs(5));
+        assert!((late[0].pos.0 - 100.0).abs() < 1e-6);
+
+        group.lift(Some(0));
+        resampler.update(&group);
+        assert!(resampler.sample(Duration::from_secs(1)).is_empty());
+    }
+}
+
+fn interpolate(last: Sample, next: Sample, sample_time: Duration) -> ContactSample {
+    let span = (next.time.as_secs_f64() - last.time.as_secs_f64()).max(f64::EPSILON);
+    let velocity = ((next.x - last.x) / span, (next.y - last.y) / span);
+
+    // clamp the sample time into the available range
+    let t = (sample_time.as_secs_f64() - last.time.as_secs_f64()).clamp(0.0, span);
+    let pos = (last.x + velocity.0 * t, last.y + velocity.1 * t);
+
+    ContactSample {
+        slot: None,
+        pos,
+        velocity,
+    }
+}
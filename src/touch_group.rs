@@ -1,15 +1,24 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 #[derive(Debug, Clone)]
 pub struct TouchPos {
     pub cur_pos: (Option<i32>, Option<i32>),
-    pub prev_pos: (Option<i32>, Option<i32>), // 第一次触摸没有prev
+    pub cur_time: Option<Duration>,
+    pub major: Option<i32>, // 接触面积/压力，用于拒绝手掌
+    touch_major: bool,      // `major` came from ABS_MT_TOUCH_MAJOR
+}
+
+/// One live contact, keyed in [`TouchGroup`] by its slot — the protocol's real
+/// identity in evdev MT-B
+#[derive(Debug, Clone)]
+pub struct Contact {
+    pub id: Option<i32>,
+    pub pos: TouchPos,
 }
 
 #[derive(Debug, Clone)]
 pub struct TouchGroup {
-    pub id_slot: HashMap<i32, Option<i32>>,
-    pub slot_pos: HashMap<Option<i32>, TouchPos>,
+    pub slots: HashMap<Option<i32>, Contact>,
 }
 
 impl TouchPos {
@@ -17,38 +26,73 @@ impl TouchPos {
     pub const fn new() -> Self {
         Self {
             cur_pos: (None, None),
-            prev_pos: (None, None),
+            cur_time: None,
+            major: None,
+            touch_major: false,
         }
     }
 
-    pub fn x(&mut self, pos_x: i32) {
-        self.prev_pos = self.cur_pos;
+    pub fn x(&mut self, pos_x: i32, time: Duration) {
+        self.cur_time = Some(time);
         self.cur_pos.0 = Some(pos_x);
     }
 
-    pub fn y(&mut self, pos_y: i32) {
-        self.prev_pos = self.cur_pos;
+    pub fn y(&mut self, pos_y: i32, time: Duration) {
+        self.cur_time = Some(time);
         self.cur_pos.1 = Some(pos_y);
     }
+
+    /// Record the contact's major-axis size (`ABS_MT_TOUCH_MAJOR`), used by the
+    /// palm-rejection filter
+    pub fn major(&mut self, major: i32) {
+        self.major = Some(major);
+        self.touch_major = true;
+    }
+
+    /// Record the contact's pressure (`ABS_MT_PRESSURE`) as a fallback size when
+    /// the panel does not report `ABS_MT_TOUCH_MAJOR`
+    pub fn pressure(&mut self, pressure: i32) {
+        if !self.touch_major {
+            self.major = Some(pressure);
+        }
+    }
 }
 
 impl TouchGroup {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            id_slot: HashMap::new(),
-            slot_pos: HashMap::new(),
+            slots: HashMap::new(),
         }
     }
 
-    pub fn remove_id(&mut self) {
-        let Some(id) = self.id_slot.keys().max().copied() else {
-            return;
-        };
+    /// Select or create the contact on `slot`, (re)assigning its tracking `id`
+    pub fn touch(&mut self, slot: Option<i32>, id: i32) {
+        self.slots
+            .entry(slot)
+            .or_insert_with(|| Contact {
+                id: Some(id),
+                pos: TouchPos::new(),
+            })
+            .id = Some(id);
+    }
 
-        if let Some(slot) = self.id_slot.get(&id) {
-            self.slot_pos.remove(slot);
-        }
-        self.id_slot.remove(&id);
+    /// Lift the contact on `slot`
+    ///
+    /// In the evdev MT-B protocol a finger lift is reported on the currently
+    /// selected slot via `tracking_id = -1`, not on the largest tracking id, so
+    /// removal is keyed by slot.
+    pub fn lift(&mut self, slot: Option<i32>) {
+        self.slots.remove(&slot);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
     }
 }
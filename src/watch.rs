@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    sync::{
+        atomic::AtomicUsize,
+        mpsc::{Sender, SyncSender},
+        Arc, Mutex, RwLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use atomic::Atomic;
+use evdev::{Device, EventType};
+
+use super::{
+    gesture::Gesture, read, resample::Resampler, ContactSnapshot, ContactsMap, SamplerMap,
+    StatusMap, TouchStatus,
+};
+
+/// Knobs threaded from [`TouchListener`] into each listening thread
+///
+/// Grouping these keeps the per-device spawn signature manageable as more
+/// tunables are added.
+#[derive(Clone)]
+pub(crate) struct Shared {
+    pub gestures: Sender<Gesture>,
+    pub min_pixel: Arc<AtomicUsize>,
+    pub swipe_pixel: Arc<AtomicUsize>,
+    pub pinch_percent: Arc<AtomicUsize>,
+    pub min_lifetime: Arc<AtomicUsize>,
+    pub palm_major: Arc<AtomicUsize>,
+    pub debounce: Arc<AtomicUsize>,
+}
+
+/// Interval between `/dev/input` rescans
+const RESCAN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Open `eventN`, allocate its shared state and spawn a [`read::daemon_thread`]
+///
+/// Seeds `status_map`, `samplers` and `contacts_map` before the listening thread
+/// produces anything, and drops all three entries again when that thread
+/// unwinds (the device was unplugged).
+pub(crate) fn spawn_device(
+    id: usize,
+    device: Device,
+    status_map: &Arc<RwLock<StatusMap>>,
+    samplers: &Arc<RwLock<SamplerMap>>,
+    contacts_map: &Arc<RwLock<ContactsMap>>,
+    notice: &SyncSender<()>,
+    shared: &Shared,
+) -> Result<(), std::io::Error> {
+    let touch_status = Arc::new(Atomic::new(TouchStatus::None));
+    let resampler = Arc::new(Mutex::new(Resampler::new()));
+    let contacts: Arc<RwLock<Vec<ContactSnapshot>>> = Arc::new(RwLock::new(Vec::new()));
+
+    let touch_status_clone = touch_status.clone();
+    let resampler_clone = resampler.clone();
+    let contacts_clone = contacts.clone();
+    let status_map_thread = status_map.clone();
+    let samplers_thread = samplers.clone();
+    let contacts_map_thread = contacts_map.clone();
+    let notice = notice.clone();
+    let shared = shared.clone();
+
+    // Seed the maps before spawning: a thread that returns quickly (a non-ABSOLUTE
+    // node, or an instant ENODEV) must never run its cleanup before the entry exists,
+    // or it would leave a permanent stale entry reporting `None` forever.
+    status_map.write().unwrap().insert(id, touch_status);
+    samplers.write().unwrap().insert(id, resampler);
+    contacts_map.write().unwrap().insert(id, contacts);
+
+    thread::Builder::new()
+        .name("TouchDeviceListener".into())
+        .spawn(move || {
+            read::daemon_thread(
+                device,
+                &touch_status_clone,
+                &notice,
+                &resampler_clone,
+                &shared.gestures,
+                &shared.swipe_pixel,
+                &shared.pinch_percent,
+                &read::FilterAtomics {
+                    min_pixel: &shared.min_pixel,
+                    min_lifetime: &shared.min_lifetime,
+                    palm_major: &shared.palm_major,
+                    debounce: &shared.debounce,
+                },
+                &contacts_clone,
+            );
+            // fetch_events returned ENODEV (device unplugged): drop the stale entries
+            if let Ok(mut map) = status_map_thread.write() {
+                map.remove(&id);
+            }
+            if let Ok(mut map) = samplers_thread.write() {
+                map.remove(&id);
+            }
+            if let Ok(mut map) = contacts_map_thread.write() {
+                map.remove(&id);
+            }
+        })?;
+
+    Ok(())
+}
+
+/// Watch `/dev/input` for hot-plugged touch devices
+///
+/// Periodically rescans the node list; any newly appearing `eventN` whose
+/// [`supported_events`] contains [`EventType::ABSOLUTE`] is opened and handed to
+/// [`spawn_device`]. Removal is handled by the device thread itself, which exits
+/// and drops its own`status_map`entry when`fetch_events`reports`ENODEV`.
+///
+/// [`supported_events`]: evdev::Device::supported_events
+pub(crate) fn watcher(
+    status_map: Arc<RwLock<StatusMap>>,
+    samplers: Arc<RwLock<SamplerMap>>,
+    contacts_map: Arc<RwLock<ContactsMap>>,
+    notice: SyncSender<()>,
+    shared: Shared,
+) {
+    loop {
+        thread::sleep(RESCAN_INTERVAL);
+
+        let Ok(dir) = fs::read_dir("/dev/input") else {
+            continue;
+        };
+
+        for entry in dir.filter_map(Result::ok) {
+            let Some(id) = parse_event_id(&entry.file_name().into_string().ok().unwrap_or_default())
+            else {
+                continue;
+            };
+
+            if status_map.read().map(|m| m.contains_key(&id)).unwrap_or(true) {
+                continue; // already listening (or map poisoned)
+            }
+
+            let Ok(device) = Device::open(entry.path()) else {
+                continue;
+            };
+
+            if !device.supported_events().contains(EventType::ABSOLUTE) {
+                continue;
+            }
+
+            let _ = spawn_device(
+                id,
+                device,
+                &status_map,
+                &samplers,
+                &contacts_map,
+                &notice,
+                &shared,
+            );
+        }
+    }
+}
+
+/// Parse the trailing number of an `eventN` node name
+///
+/// Gated on the `event` prefix so other nodes (e.g. `mouse0`) don't alias onto
+/// an `eventN` id.
+pub(crate) fn parse_event_id(name: &str) -> Option<usize> {
+    name.strip_prefix("event")?.trim().parse().ok()
+}